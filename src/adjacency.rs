@@ -0,0 +1,53 @@
+/// a fixed-capacity, stack-allocated list of at most 8 adjacent grid cell indices.
+/// `Minesweeper::adjacent_indices` is called repeatedly during flood fill and mine-count
+/// setup, so it returns this instead of a `Vec<usize>` to avoid a heap allocation per call
+#[derive(Clone, Copy)]
+pub(crate) struct AdjacentIndices {
+    buf: [usize; 8],
+    len: usize,
+}
+
+impl AdjacentIndices {
+    pub(crate) fn new() -> AdjacentIndices {
+        AdjacentIndices { buf: [0; 8], len: 0 }
+    }
+
+    pub(crate) fn push(&mut self, index: usize) {
+        self.buf[self.len] = index;
+        self.len += 1;
+    }
+
+    pub(crate) fn iter(&self) -> std::slice::Iter<usize> {
+        self.buf[..self.len].iter()
+    }
+}
+
+/// owning iterator over an `AdjacentIndices`' elements
+pub(crate) struct AdjacentIndicesIntoIter {
+    buf: [usize; 8],
+    idx: usize,
+    len: usize,
+}
+
+impl Iterator for AdjacentIndicesIntoIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.idx < self.len {
+            let ndx = self.buf[self.idx];
+            self.idx += 1;
+            Some(ndx)
+        } else {
+            None
+        }
+    }
+}
+
+impl IntoIterator for AdjacentIndices {
+    type Item = usize;
+    type IntoIter = AdjacentIndicesIntoIter;
+
+    fn into_iter(self) -> AdjacentIndicesIntoIter {
+        AdjacentIndicesIntoIter { buf: self.buf, idx: 0, len: self.len }
+    }
+}
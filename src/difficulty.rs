@@ -0,0 +1,70 @@
+use wasm_bindgen::prelude::*;
+
+use crate::Minesweeper;
+
+/// a board preset controlling the grid dimensions and exact mine count used by
+/// `Minesweeper::init_with_difficulty`. Not exposed directly to JS: wasm-bindgen can't export
+/// an enum with data-carrying variants like `Custom`, so the WASM-facing surface is instead the
+/// `init_beginner`/`init_intermediate`/`init_expert`/`init_custom` constructors below
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    /// 9x9 grid, 10 mines
+    Beginner,
+    /// 16x16 grid, 40 mines
+    Intermediate,
+    /// 16x30 grid, 99 mines
+    Expert,
+    /// an arbitrary grid size with an exact mine count
+    Custom { rows: usize, cols: usize, mines: usize },
+}
+
+impl Difficulty {
+    /// returns this difficulty's `(num_rows, num_cols, total_mines)`
+    fn dimensions(&self) -> (usize, usize, usize) {
+        match *self {
+            Difficulty::Beginner => (9, 9, 10),
+            Difficulty::Intermediate => (16, 16, 40),
+            Difficulty::Expert => (16, 30, 99),
+            Difficulty::Custom { rows, cols, mines } => (rows, cols, mines),
+        }
+    }
+}
+
+impl Minesweeper {
+    /// initializes a new MineSweeper grid sized for the given `Difficulty`, placing exactly
+    /// that difficulty's mine count rather than a percentage of the grid. Mine placement is
+    /// still deferred until the first `reveal_cell`, same as `init_with_seed`. If the mine count
+    /// exceeds the cells left once the first revealed cell and its neighbors are excluded (only
+    /// possible for a dense `Custom` board), only that many mines are placed and `total_mines`
+    /// is lowered to match
+    pub fn init_with_difficulty(difficulty: Difficulty, seed: u64) -> Minesweeper {
+        let (num_rows, num_cols, total_mines) = difficulty.dimensions();
+        let mut game = Minesweeper::init_with_seed(num_rows, num_cols, seed);
+        game.total_mines = total_mines;
+        game
+    }
+}
+
+#[wasm_bindgen]
+impl Minesweeper {
+    /// initializes the standard "Beginner" preset: a 9x9 grid with 10 mines
+    pub fn init_beginner(seed: u64) -> Minesweeper {
+        Minesweeper::init_with_difficulty(Difficulty::Beginner, seed)
+    }
+
+    /// initializes the standard "Intermediate" preset: a 16x16 grid with 40 mines
+    pub fn init_intermediate(seed: u64) -> Minesweeper {
+        Minesweeper::init_with_difficulty(Difficulty::Intermediate, seed)
+    }
+
+    /// initializes the standard "Expert" preset: a 16x30 grid with 99 mines
+    pub fn init_expert(seed: u64) -> Minesweeper {
+        Minesweeper::init_with_difficulty(Difficulty::Expert, seed)
+    }
+
+    /// initializes a grid of the given size with an exact mine count, for difficulties other
+    /// than the standard presets
+    pub fn init_custom(rows: usize, cols: usize, mines: usize, seed: u64) -> Minesweeper {
+        Minesweeper::init_with_difficulty(Difficulty::Custom { rows, cols, mines }, seed)
+    }
+}
@@ -1,9 +1,15 @@
 mod utils;
+mod adjacency;
 pub mod mine_sweeper_cell;
+pub mod solver;
+pub mod replay;
+pub mod difficulty;
 
 use wasm_bindgen::prelude::*;
+use adjacency::AdjacentIndices;
 use mine_sweeper_cell::{Cell, CellState, CellKind};
-use js_sys::Math::{random, floor};
+use replay::Move;
+use js_sys::Math::random;
 use std::collections::HashSet;
 use std::fmt;
 
@@ -31,6 +37,10 @@ pub struct Minesweeper {
     grid: Vec<Cell>,
     num_rows: usize,
     num_cols: usize,
+    seed: u64,
+    moves: Vec<Move>,
+    mines_placed: bool,
+    total_mines: usize,
 }
 
 
@@ -47,10 +57,20 @@ impl Minesweeper {
         grid
     }
 
-    /// shuffle the elements in a vector using Knuth's shuffle
-    fn shuffle(v: &mut Vec<usize>) {
+    /// advances a simple 64-bit linear congruential generator and returns a `usize` drawn
+    /// from its high bits, reduced modulo `bound`
+    /// this keeps mine placement reproducible: the same `seed` always produces the same
+    /// sequence of draws, unlike `js_sys::Math::random`
+    fn next_lcg(state: &mut u64, bound: usize) -> usize {
+        *state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        ((*state >> 33) as usize) % bound
+    }
+
+    /// shuffle the elements in a vector using Knuth's shuffle, drawing swap indices from the
+    /// seeded LCG in `state`
+    fn shuffle(v: &mut Vec<usize>, state: &mut u64) {
         for i in (1..v.len()).rev() {
-            let ridx = floor( random() * i as f64) as usize;
+            let ridx = Minesweeper::next_lcg(state, i + 1);
             v.swap(i, ridx);
         }
     }
@@ -58,17 +78,21 @@ impl Minesweeper {
     /// Generates `count` amount of random grid indices ranging from 0..`len` and returns them
     /// in a Vector<usize>
     /// `len` is the max index value (exclusive) to use for generating indices
-    fn gen_rand_grid_indices(len: usize, count: usize) -> Vec<usize> {
+    /// `state` is the seeded LCG state driving the shuffle, so the returned indices are
+    /// reproducible for a given seed
+    fn gen_rand_grid_indices(len: usize, count: usize, state: &mut u64) -> Vec<usize> {
         // build a vec of all grid indices in row major form and shuffle them
         let mut grid_indices: Vec<usize> = (0..len).map(|i| i).collect();
-        Minesweeper::shuffle( &mut grid_indices );
+        Minesweeper::shuffle( &mut grid_indices, state );
         grid_indices.into_iter().take(count).collect()
     }
 
     /// returns the **indices** of all grid cells "adjacent" to the cell located at `index`, but
-    /// does not include the cell at `index`
-    fn adjacent_indices(num_rows: usize, num_cols: usize, index: usize) -> Vec<usize> {
-        let mut adj_ndxs = vec![];
+    /// does not include the cell at `index`. Returned as a stack-allocated `AdjacentIndices`
+    /// (a cell has at most 8 neighbors) rather than a `Vec`, since this is called repeatedly
+    /// during flood fill and mine-count setup
+    fn adjacent_indices(num_rows: usize, num_cols: usize, index: usize) -> AdjacentIndices {
+        let mut adj_ndxs = AdjacentIndices::new();
         let r = index / num_cols;
         let c = index % num_cols;
         let rstart = if r <= 1 { 0 } else { r - 1 };
@@ -99,7 +123,7 @@ impl Minesweeper {
     /// are "lone cells". Lone cells are cells that are not adjacent to any mines
     /// This function is essentially an implementation of flood fill algorithm using depth first search
     fn connected_lone_cell_indices(&self, index: usize) -> Vec<usize> {
-        let mut visited = vec![];         // cells already visited
+        let mut visited = vec![false; self.grid.len()]; // bitmap of cells already visited, indexed by grid index
         let mut to_visit = vec![ index ]; // cells left to visit
         let mut connected_ndxs = vec![];  // holds the connected cell indices
 
@@ -107,7 +131,7 @@ impl Minesweeper {
             // current index being visited
             let cur_ndx = to_visit.pop().unwrap();
 
-            if visited.contains(&cur_ndx) {
+            if visited[cur_ndx] {
                 continue;
             } else {
                 // add lone cell's index to the list of connected cell indices
@@ -116,7 +140,7 @@ impl Minesweeper {
                 }
 
                 // mark the current cell as visited
-                visited.push(cur_ndx);
+                visited[cur_ndx] = true;
 
                 // build a list of "lone" cells adjacent to the current cell
                 let mut adj_ndxs = Minesweeper::adjacent_indices(self.num_rows, self.num_cols, cur_ndx)
@@ -167,33 +191,98 @@ impl Minesweeper {
     /// MineSweeperGame Trait Impl
 
     /// initialize a new MineSweeper grid with the specified rows and columns
-    /// This function will generate random mine locations and compute the adjacent mine counts
-    /// for every cell in the grid
+    /// the seed is chosen with `js_sys::Math::random()`; mine locations are then drawn from
+    /// that seed exactly as in `init_with_seed`. Use `init_with_seed` if you need to pick the
+    /// seed yourself, or `seed()` to read back the one chosen here
     pub fn init(num_rows: usize, num_cols: usize) -> Minesweeper {
-        let mut grid = Minesweeper::empty_grid(num_rows, num_cols);
+        let seed = (random() * u64::MAX as f64) as u64;
+        Minesweeper::init_with_seed(num_rows, num_cols, seed)
+    }
 
-        // generate random mine locations
+    /// initialize a new MineSweeper grid with the specified rows and columns. Mine placement
+    /// is deferred until the first call to `reveal_cell`, so that the opening click can never
+    /// hit a mine; see `reveal_first`. Because `reveal_first` excludes whichever cell is
+    /// revealed first from the candidate pool, calling this again with the same `num_rows`,
+    /// `num_cols`, and `seed` only reproduces an identical mine layout if the same cell is
+    /// revealed first too. For reproducing or sharing a game regardless of the opening click,
+    /// use `export_replay`/`replay` instead
+    pub fn init_with_seed(num_rows: usize, num_cols: usize, seed: u64) -> Minesweeper {
+        let grid = Minesweeper::empty_grid(num_rows, num_cols);
         let total_mines = ((num_rows * num_cols) as f32 * 0.15f32).round() as usize;
-        let mine_ndxs = Minesweeper::gen_rand_grid_indices(num_rows * num_cols, total_mines);
-        for index in mine_ndxs.iter() {
-            grid[*index] = Cell::new(CellKind::Mine);
-        }
-
-        // compute the adjacent mine counts for every cell that contains a mine
-        for index in mine_ndxs.iter() {
-            for adj_ndx in Minesweeper::adjacent_indices(num_rows, num_cols, *index) {
-                let cur_count = grid[adj_ndx].adj_mine_count() + 1;
-                grid[adj_ndx].set_adj_mine_count(cur_count);
-            }
-        }
 
         Minesweeper {
             grid,
             num_rows,
             num_cols,
+            seed,
+            moves: Vec::new(),
+            mines_placed: false,
+            total_mines,
         }
     }
 
+    /// if `true`, the 8 cells adjacent to the first revealed cell are also protected from
+    /// containing a mine, not just the clicked cell itself. This typically guarantees the
+    /// opening click floods open a sizable region, matching other minesweeper implementations
+    const EXCLUDE_FIRST_CLICK_NEIGHBORS: bool = true;
+
+    /// places this game's mines for the first time, using its seed but excluding `index` (and,
+    /// per `EXCLUDE_FIRST_CLICK_NEIGHBORS`, `index`'s adjacent cells) from mine placement, then
+    /// computes the adjacent mine counts for every cell. This guarantees the player's opening
+    /// click can never immediately lose the game
+    fn reveal_first(&mut self, index: usize) {
+        let mut excluded: HashSet<usize> = HashSet::new();
+        excluded.insert(index);
+        if Minesweeper::EXCLUDE_FIRST_CLICK_NEIGHBORS {
+            excluded.extend(Minesweeper::adjacent_indices(self.num_rows, self.num_cols, index));
+        }
+
+        let total_mines = self.total_mines();
+        let mut state = self.seed;
+        let candidates = Minesweeper::gen_rand_grid_indices(
+            self.num_rows * self.num_cols,
+            self.num_rows * self.num_cols,
+            &mut state,
+        );
+        let mine_ndxs: Vec<usize> = candidates.into_iter()
+            .filter(|ndx| !excluded.contains(ndx))
+            .take(total_mines)
+            .collect();
+
+        // the excluded cells may shrink the candidate pool below `total_mines` (e.g. a custom
+        // difficulty with a very high mine density), so `total_mines` must reflect what was
+        // actually placed, not what was originally requested
+        self.total_mines = mine_ndxs.len();
+
+        for ndx in mine_ndxs.iter() {
+            self.grid[*ndx] = Cell::new(CellKind::Mine);
+        }
+
+        // compute the adjacent mine counts for every cell that contains a mine
+        for ndx in mine_ndxs.iter() {
+            for adj_ndx in Minesweeper::adjacent_indices(self.num_rows, self.num_cols, *ndx) {
+                let cur_count = self.grid[adj_ndx].adj_mine_count() + 1;
+                self.grid[adj_ndx].set_adj_mine_count(cur_count);
+            }
+        }
+
+        self.mines_placed = true;
+    }
+
+    /// returns the seed that was used to generate this grid's mine layout.
+    /// mine placement is deferred to the first `reveal_cell` (see `reveal_first`), which
+    /// excludes whichever cell is clicked first (and, by default, its neighbors) from the
+    /// candidate pool. So passing this value back into `init_with_seed` (or, for a board built
+    /// with `init_with_difficulty`, back into it with the same difficulty, since the mine count
+    /// must match too) only reproduces the exact same board if the *same cell* is revealed
+    /// first too; a different opening click
+    /// yields a different layout from the same seed. To reliably reproduce or share a finished
+    /// or in-progress game regardless of the opening click, use `export_replay`/`replay`
+    /// instead, which additionally records the moves played
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
     /// returns the locations on the grid where mines are located
     /// # Returns
     /// a Vector of usize where each element is the index of a mine on the grid
@@ -204,12 +293,11 @@ impl Minesweeper {
             .collect::<Vec<usize>>()
     }
 
-    /// compute the total number of mines that that a grid should contain based on the number
-    /// of rows and columns
-    /// Total mines on a grid is 15% * the number of cells i.e.:
-    ///     `TOTAL_MINES = grid.num_rows * grid.mum_columns * 0.15`
+    /// returns the total number of mines on this grid.
+    /// `init`/`init_with_seed` default this to 15% of the grid's cells, rounded; a board built
+    /// with `init_with_difficulty` uses that difficulty's exact mine count instead
     pub fn total_mines(&self) -> usize {
-        ((self.num_rows * self.num_cols) as f32 * 0.15f32).round() as usize
+        self.total_mines
     }
 
     /// computes the remaining number of flags that can be placed by the player
@@ -222,9 +310,15 @@ impl Minesweeper {
     }
 
     /// reveals a cell at the specified index
+    /// if this is the first reveal of the game, this also triggers mine placement via
+    /// `reveal_first` so that the opening click can never hit a mine
     /// This function marks the cell's internal state as `CellState::Revealed` and then triggers
     /// the revealing of any "lone" cells that are "connected", or adjacent to, this cell
     pub fn reveal_cell(&mut self, index: usize) {
+        self.moves.push(Move::Reveal(index));
+        if !self.mines_placed {
+            self.reveal_first(index);
+        }
         if !self.grid[index].is_revealed() {
             self.grid[index].set_state(CellState::Revealed);
             // if the revealed cell is a lone cell, then reveal connected lone cells
@@ -257,6 +351,7 @@ impl Minesweeper {
     /// sets the cell's state to flagged if it is currently Hidden, else sets the cell's state
     /// to hidden if it is currently flagged
     pub fn toggle_flag(&mut self, index: usize) {
+        self.moves.push(Move::ToggleFlag(index));
         if !self.grid[index].is_revealed() {
             if self.grid[index].is_flagged() {
                 self.grid[index].set_state(CellState::Hidden);
@@ -269,6 +364,7 @@ impl Minesweeper {
     /// sets the cell's state to questioned if it is currently Hidden, else sets the cell's state
     /// to hidden if it is currently questioned
     pub fn toggle_question(&mut self, index: usize) {
+        self.moves.push(Move::ToggleQuestion(index));
         if !self.grid[index].is_revealed() {
             if self.grid[index].is_questioned() {
                 self.grid[index].set_state(CellState::Hidden);
@@ -299,6 +395,32 @@ impl Minesweeper {
         }
     }
 
+    /// performs a "chord" on the cell at `index`: if the cell is revealed and showing a
+    /// number, and exactly that many of its adjacent cells are flagged, reveals every
+    /// remaining un-flagged adjacent cell (each via `reveal_cell`, so the flood fill still
+    /// cascades through any lone cells). This is the standard minesweeper "both buttons"
+    /// shortcut for quickly clearing cells once their mine count is satisfied. If a
+    /// mis-flagged neighbor turns out to be a mine, revealing it will correctly cause
+    /// `is_game_lost` to report the loss
+    pub fn chord_cell(&mut self, index: usize) {
+        if !self.grid[index].is_revealed() {
+            return;
+        }
+
+        let adj = Minesweeper::adjacent_indices(self.num_rows, self.num_cols, index);
+        let flagged = adj.iter().filter(|&&ndx| self.grid[ndx].is_flagged()).count();
+
+        if flagged != self.grid[index].adj_mine_count() as usize {
+            return;
+        }
+
+        for ndx in adj {
+            if !self.grid[ndx].is_flagged() {
+                self.reveal_cell(ndx);
+            }
+        }
+    }
+
     /// returns true if a cell is flagged AND contains a mine, else false
     pub fn flagged_mine_cell(&self, index: usize) -> bool {
         self.grid[index].is_flagged() && self.grid[index].is_mined()
@@ -316,9 +438,12 @@ impl Minesweeper {
     /// # Returns
     /// `true` if the game is won, `false` if the game is not yet won
     pub fn is_game_won(&self) -> bool {
-        self.mine_indices()
-            .iter()
-            .all(|&i| self.grid[i].is_flagged() )
+        // mines are placed lazily on the first reveal, so before that `mine_indices()` is
+        // empty and would otherwise report a fresh board as already won
+        self.mines_placed
+            && self.mine_indices()
+                .iter()
+                .all(|&i| self.grid[i].is_flagged() )
     }
 
     /// determines if a game of minesweeper is lost.
@@ -327,6 +452,12 @@ impl Minesweeper {
     /// # Returns
     /// `true` if the game is lost, else `false`
     pub fn is_game_lost(&self) -> bool {
+        // mines are placed lazily on the first reveal; before that there are no mines to
+        // reveal or mis-flag, so the game cannot yet be lost
+        if !self.mines_placed {
+            return false;
+        }
+
         let mine_revealed = self.mine_indices()
             .iter()
             .any(|&i| self.grid[i].is_revealed() );
@@ -0,0 +1,104 @@
+use wasm_bindgen::prelude::*;
+
+use crate::Minesweeper;
+
+/// a single player action that can be recorded and replayed against a `Minesweeper` game
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Reveal(usize),
+    ToggleFlag(usize),
+    ToggleQuestion(usize),
+}
+
+impl Move {
+    /// parses a single move from its `export_replay` encoding, e.g. `"R12"`, `"F3"`, `"Q7"`
+    fn parse(s: &str) -> Option<Move> {
+        if s.is_empty() {
+            return None;
+        }
+        let (tag, rest) = s.split_at(1);
+        let index: usize = rest.parse().ok()?;
+        match tag {
+            "R" => Some(Move::Reveal(index)),
+            "F" => Some(Move::ToggleFlag(index)),
+            "Q" => Some(Move::ToggleQuestion(index)),
+            _ => None,
+        }
+    }
+
+    /// encodes this move the way `export_replay` expects, e.g. `"R12"`, `"F3"`, `"Q7"`
+    fn encode(&self) -> String {
+        match self {
+            Move::Reveal(ndx) => format!("R{}", ndx),
+            Move::ToggleFlag(ndx) => format!("F{}", ndx),
+            Move::ToggleQuestion(ndx) => format!("Q{}", ndx),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Minesweeper {
+    /// applies a single recorded `Move` to this game, exactly as if the player had performed it
+    fn apply_move(&mut self, mv: Move) {
+        match mv {
+            Move::Reveal(ndx) => self.reveal_cell(ndx),
+            Move::ToggleFlag(ndx) => self.toggle_flag(ndx),
+            Move::ToggleQuestion(ndx) => self.toggle_question(ndx),
+        }
+    }
+
+    /// serializes this game's seed, dimensions, mine count, and full move log into a single
+    /// string that `replay` can later reconstruct
+    pub fn export_replay(&self) -> String {
+        let moves_str = self.moves.iter()
+            .map(Move::encode)
+            .collect::<Vec<String>>()
+            .join(";");
+        format!(
+            "{},{},{},{}|{}",
+            self.seed, self.num_rows, self.num_cols, self.total_mines, moves_str
+        )
+    }
+
+    /// reconstructs a `Minesweeper` game from a string produced by `export_replay`: rebuilds
+    /// the initial board from the seed and mine count, then re-applies every recorded move in
+    /// order, yielding the final game state
+    pub fn replay(serialized: &str) -> Minesweeper {
+        let mut sections = serialized.splitn(2, '|');
+        let header = sections.next().unwrap_or_default();
+        let moves_str = sections.next().unwrap_or_default();
+
+        let mut header_parts = header.split(',');
+        let seed: u64 = header_parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let num_rows: usize = header_parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let num_cols: usize = header_parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let total_mines: Option<usize> = header_parts.next().and_then(|s| s.parse().ok());
+
+        let mut game = Minesweeper::init_with_seed(num_rows, num_cols, seed);
+        if let Some(total_mines) = total_mines {
+            game.total_mines = total_mines;
+        }
+        if !moves_str.is_empty() {
+            for mv in moves_str.split(';').filter_map(Move::parse) {
+                game.apply_move(mv);
+            }
+        }
+        game
+    }
+
+    /// the number of moves recorded so far for this game
+    pub fn step_count(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// replays this game's recorded moves from the start up to (but not including) `step`,
+    /// returning the board state at that point. Lets a front-end scrub through a finished game
+    pub fn replay_to(&self, step: usize) -> Minesweeper {
+        let mut game = Minesweeper::init_with_seed(self.num_rows, self.num_cols, self.seed);
+        game.total_mines = self.total_mines;
+        for mv in self.moves.iter().take(step).copied() {
+            game.apply_move(mv);
+        }
+        game
+    }
+}
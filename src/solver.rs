@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+
+use wasm_bindgen::prelude::*;
+
+use crate::Minesweeper;
+
+/// outcome of a single constraint-propagation pass over the currently visible board: cell
+/// indices that are provably mines, and cell indices that are provably safe to reveal
+/// the index lists are exposed to JS via getter methods rather than public fields, since
+/// wasm-bindgen struct fields must be JS-primitive types
+#[wasm_bindgen]
+pub struct SolveResult {
+    mines: Vec<usize>,
+    safe: Vec<usize>,
+}
+
+#[wasm_bindgen]
+impl SolveResult {
+    /// the indices of cells that are provably mines
+    pub fn mines(&self) -> Vec<u32> {
+        self.mines.iter().map(|&ndx| ndx as u32).collect()
+    }
+
+    /// the indices of cells that are provably safe to reveal
+    pub fn safe(&self) -> Vec<u32> {
+        self.safe.iter().map(|&ndx| ndx as u32).collect()
+    }
+}
+
+/// a constraint derived from one revealed numbered cell: `remaining` mines are distributed
+/// somewhere among the cells in `unknown`
+struct Constraint {
+    unknown: HashSet<usize>,
+    remaining: usize,
+}
+
+#[wasm_bindgen]
+impl Minesweeper {
+    /// performs one constraint-propagation pass over the currently visible board and returns
+    /// the cell indices that the rules below prove to be a mine, or prove to be safe to reveal,
+    /// without guessing. Deductions that need reasoning beyond these rules are not found
+    ///
+    /// every revealed numbered cell is modeled as a constraint over its hidden, un-flagged
+    /// neighbors. two rules are applied to a fixpoint: if a constraint's remaining mine count
+    /// equals its number of unknown neighbors, every neighbor is a mine; if the remaining count
+    /// is zero, every neighbor is safe. a subset rule is then applied: given constraints over
+    /// unknown-cell sets `a` ⊆ `b`, the cells in `b \ a` contain exactly `b.remaining -
+    /// a.remaining` mines, which yields further deductions. A hidden cell can be mis-flagged
+    /// (flagging never validates correctness), which can make `b.remaining < a.remaining`; such
+    /// constraints are inconsistent with each other rather than a deduction opportunity, so
+    /// they're skipped instead of underflowing
+    ///
+    /// useful for a "hint" or auto-play feature, and for verifying a generated board has a
+    /// logically solvable opening
+    pub fn solve_step(&self) -> SolveResult {
+        let mut constraints = self.build_constraints();
+
+        let mut mines: HashSet<usize> = HashSet::new();
+        let mut safe: HashSet<usize> = HashSet::new();
+
+        loop {
+            let mut changed = false;
+
+            // base rules: all-mine / all-safe constraints
+            for c in constraints.iter() {
+                if c.remaining == c.unknown.len() {
+                    for &ndx in &c.unknown {
+                        changed |= mines.insert(ndx);
+                    }
+                } else if c.remaining == 0 {
+                    for &ndx in &c.unknown {
+                        changed |= safe.insert(ndx);
+                    }
+                }
+            }
+
+            // subset rule: for constraints a ⊆ b, b \ a contains exactly b.remaining -
+            // a.remaining mines
+            for i in 0..constraints.len() {
+                for j in 0..constraints.len() {
+                    if i == j {
+                        continue;
+                    }
+                    let (a, b) = (&constraints[i], &constraints[j]);
+                    if a.unknown.len() < b.unknown.len() && a.unknown.is_subset(&b.unknown) {
+                        // `b.remaining < a.remaining` means the two constraints disagree about
+                        // the cells they share, which only happens when a hidden cell has been
+                        // mis-flagged. that's not a deduction opportunity, so skip it rather
+                        // than underflow the subtraction
+                        let diff_mines = match b.remaining.checked_sub(a.remaining) {
+                            Some(diff_mines) => diff_mines,
+                            None => continue,
+                        };
+                        let diff: Vec<usize> = b.unknown.difference(&a.unknown).copied().collect();
+                        if diff_mines == diff.len() {
+                            for ndx in diff {
+                                changed |= mines.insert(ndx);
+                            }
+                        } else if diff_mines == 0 {
+                            for ndx in diff {
+                                changed |= safe.insert(ndx);
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+
+            // remove newly-known cells from every constraint's unknown set, adjusting its
+            // remaining mine count so the next pass sees the simplified constraints
+            for c in constraints.iter_mut() {
+                let removed_mines = c.unknown.iter().filter(|ndx| mines.contains(ndx)).count();
+                c.unknown.retain(|ndx| !mines.contains(ndx) && !safe.contains(ndx));
+                // a constraint whose flagging is already inconsistent with a deduced mine can
+                // have fewer actual mines left than cells removed; saturate instead of
+                // underflowing so one bad deduction doesn't poison the rest of the pass
+                c.remaining = c.remaining.saturating_sub(removed_mines);
+            }
+            constraints.retain(|c| !c.unknown.is_empty());
+        }
+
+        SolveResult {
+            mines: mines.into_iter().collect(),
+            safe: safe.into_iter().collect(),
+        }
+    }
+
+    /// builds one `Constraint` per revealed numbered cell, covering its hidden, un-flagged
+    /// neighbors
+    fn build_constraints(&self) -> Vec<Constraint> {
+        let mut constraints = Vec::new();
+
+        for index in 0..self.grid.len() {
+            let cell = &self.grid[index];
+            if !cell.is_revealed() || cell.adj_mine_count() == 0 {
+                continue;
+            }
+
+            let adj = Minesweeper::adjacent_indices(self.num_rows, self.num_cols, index);
+            let flagged = adj.iter().filter(|&&ndx| self.grid[ndx].is_flagged()).count();
+            let unknown: HashSet<usize> = adj.iter()
+                .copied()
+                .filter(|&ndx| !self.grid[ndx].is_revealed() && !self.grid[ndx].is_flagged())
+                .collect();
+
+            if unknown.is_empty() {
+                continue;
+            }
+
+            constraints.push(Constraint {
+                unknown,
+                remaining: (cell.adj_mine_count() as usize).saturating_sub(flagged),
+            });
+        }
+
+        constraints
+    }
+}